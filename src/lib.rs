@@ -8,6 +8,15 @@
 //! It behaves the same as nested for loops and brings the advantage of
 //! being more compact, and simplifies breaking and continuing.
 //!
+//! The returned iterator is an [`ExactSizeIterator`] and [`DoubleEndedIterator`], so it reports
+//! an exact length and can be iterated back-to-front. Only `cartesian!`'s *first* argument needs
+//! to be an `ExactSizeIterator + DoubleEndedIterator` itself (it is streamed lazily); every other
+//! argument is buffered into a `Vec` up front and so only needs to yield `Clone` items, e.g.
+//! `HashMap::iter()` or a `.filter()` chain works fine in any position but the first.
+//!
+//! Besides `cartesian!`, this crate also provides [`cartesian_power!`] for the `N`-th Cartesian
+//! power of a single iterator, and [`multi_product`] for a runtime-sized collection of iterators.
+//!
 //! # Examples
 //! ```
 //! use cartesian::cartesian;
@@ -38,6 +47,10 @@
 //! assert_eq!(col_vec, res);
 //! ```
 
+extern crate alloc;
+
+use alloc::vec::Vec;
+
 /// Helper trait implemented for all tuples up to 26 elements to prepend a value to produce a longer tuple
 ///
 /// The implementation is adapted from [this stackoverflow answer](https://stackoverflow.com/a/57454888).
@@ -91,25 +104,467 @@ macro_rules! cartesian {
         cartesian!(@ $head.into_iter(), $tail.into_iter())
     };
 
-    // Expression                                              | Type
-    // --------------------------------------------------------+----------------------------------------
-    // $head.into_iter()                                       | impl Iterator<Item = A>
-    // cartesian!($($tail),+)                                  | impl Iterator<Item = (B, C, ...)>
-    // cartesian!(@ $head.into_iter(), cartesian!($($tail),+)) | impl Iterator<Item = (A, (B, C, ...))>
-    // cartesian!(...).map(...)                                | impl Iterator<Item = (A, B, C, ...)>
+    // Only `$head` plays the role of the leading dimension of the whole product, so it's the
+    // only argument that needs `ExactSizeIterator` (see `Product`). The remaining arguments are
+    // combined with `_cartesian_buffered!` instead of recursing back into `cartesian!` itself,
+    // so they only ever need to yield `Clone` items, not be re-iterated from a live `head` position.
+    //
+    // Expression                                                       | Type
+    // ------------------------------------------------------------------+---------------------------------
+    // $head.into_iter()                                                 | impl Iterator<Item = A>
+    // _cartesian_buffered!($($tail),+)                                  | impl Iterator<Item = (B, C, ...)>
+    // cartesian!(@ $head.into_iter(), _cartesian_buffered!($($tail),+)) | impl Iterator<Item = (A, (B, C, ...))>
+    // cartesian!(...).map(...)                                          | impl Iterator<Item = (A, B, C, ...)>
     ($head:expr $(, $tail:expr)+ $(,)?) => {
-        cartesian!(@ $head.into_iter(), cartesian!($($tail),+)).map(
+        cartesian!(@ $head.into_iter(), $crate::_cartesian_buffered!($($tail),+)).map(
             |(head, tail)| $crate::TuplePrepend::prepend(tail, head)
         )
     };
 
     (@ $head:expr, $tail:expr $(,)?) => {
-        $head.flat_map(|h| $tail.map(move |t| (h, t)))
+        $crate::Product::new($head, $tail)
     };
 }
 
-#[cfg(test)]
-extern crate alloc;
+/// Implementation detail of [`cartesian!`]: combines every dimension but the first one.
+///
+/// Mirrors `cartesian!`'s own recursion, but nests [`TailProduct`] instead of [`Product`], so
+/// every argument here only ever needs to yield `Clone` items rather than be `ExactSizeIterator +
+/// DoubleEndedIterator`. Not part of the public API; only exported because `macro_rules!`
+/// macros must be reachable via `$crate` from `cartesian!`'s expansion site.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! _cartesian_buffered {
+    ($head:expr $(,)?) => {
+        $head.into_iter()
+    };
+
+    ($head:expr, $tail:expr $(,)?) => {
+        $crate::TailProduct::new($head.into_iter(), $tail.into_iter())
+    };
+
+    ($head:expr $(, $tail:expr)+ $(,)?) => {
+        $crate::TailProduct::new($head.into_iter(), $crate::_cartesian_buffered!($($tail),+)).map(
+            |(head, tail)| $crate::TuplePrepend::prepend(tail, head)
+        )
+    };
+}
+
+/// Concrete iterator type returned by [`cartesian!`] for a pair of dimensions.
+///
+/// Unlike the `flat_map`/`map` chain this replaces, `Product` buffers the trailing
+/// dimension into a `Vec` up front, which lets it track exactly how many items remain
+/// (so it implements [`ExactSizeIterator`] with an exact `size_hint`, useful for callers
+/// pre-allocating e.g. `Vec::with_capacity` before iterating a grid). The leading
+/// dimension is pulled lazily, one item at a time, from both ends, so a large leading
+/// dimension costs no extra memory whichever end it is iterated from.
+///
+/// `next` and `next_back` each hold at most one leading-dimension item at a time; once
+/// both ends are narrowed down to the same leading item they share the remaining slice
+/// of the tail buffer instead of re-reading the leading dimension again.
+///
+/// [`cartesian!`] only ever combines its very first argument through `Product`; every other
+/// argument is combined with [`TailProduct`] instead, so only the true leading dimension of a
+/// `cartesian!` call needs `ExactSizeIterator + DoubleEndedIterator` — the rest just need to
+/// yield `Clone` items.
+pub struct Product<A, B>
+where
+    A: Iterator,
+{
+    head: A,
+    tail: Vec<B>,
+    rows_left: usize,
+    front_head: Option<A::Item>,
+    back_head: Option<A::Item>,
+    front_idx: usize,
+    back_idx: usize,
+    shared: bool,
+    remaining: usize,
+}
+
+impl<A, B> Product<A, B>
+where
+    A: ExactSizeIterator + DoubleEndedIterator,
+    A::Item: Clone,
+    B: Clone,
+{
+    pub fn new<T: IntoIterator<Item = B>>(head: A, tail: T) -> Self {
+        let tail: Vec<B> = tail.into_iter().collect();
+        let rows_left = head.len();
+        let remaining = rows_left * tail.len();
+        let back_idx = tail.len();
+        Product {
+            head,
+            tail,
+            rows_left,
+            front_head: None,
+            back_head: None,
+            front_idx: 0,
+            back_idx,
+            shared: false,
+            remaining,
+        }
+    }
+
+    /// Ensures `front_head` holds a leading-dimension item to draw from, pulling a
+    /// fresh one from `head` (or, once `head` is exhausted, sharing whatever
+    /// `back_head` is already sitting on) if necessary.
+    fn fill_front(&mut self) -> bool {
+        if self.front_head.is_some() {
+            return true;
+        }
+        if self.rows_left > 0 {
+            self.rows_left -= 1;
+            self.front_head = self.head.next();
+            self.front_idx = 0;
+            return self.front_head.is_some();
+        }
+        if !self.shared {
+            if let Some(back_head) = &self.back_head {
+                self.front_head = Some(back_head.clone());
+                self.front_idx = 0;
+                self.shared = true;
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Mirror of [`Product::fill_front`] for the back cursor.
+    fn fill_back(&mut self) -> bool {
+        if self.back_head.is_some() {
+            return true;
+        }
+        if self.rows_left > 0 {
+            self.rows_left -= 1;
+            self.back_head = self.head.next_back();
+            self.back_idx = self.tail.len();
+            return self.back_head.is_some();
+        }
+        if !self.shared {
+            if let Some(front_head) = &self.front_head {
+                self.back_head = Some(front_head.clone());
+                self.back_idx = self.tail.len();
+                self.shared = true;
+                return true;
+            }
+        }
+        false
+    }
+}
+
+impl<A, B> Iterator for Product<A, B>
+where
+    A: ExactSizeIterator + DoubleEndedIterator,
+    A::Item: Clone,
+    B: Clone,
+{
+    type Item = (A::Item, B);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        loop {
+            if !self.fill_front() {
+                self.remaining = 0;
+                return None;
+            }
+            let upper = if self.shared {
+                self.back_idx
+            } else {
+                self.tail.len()
+            };
+            if self.front_idx >= upper {
+                self.front_head = None;
+                continue;
+            }
+
+            let head = self.front_head.clone().expect("front_head filled above");
+            let item = (head, self.tail[self.front_idx].clone());
+            self.front_idx += 1;
+            self.remaining -= 1;
+            return Some(item);
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<A, B> ExactSizeIterator for Product<A, B>
+where
+    A: ExactSizeIterator + DoubleEndedIterator,
+    A::Item: Clone,
+    B: Clone,
+{
+    fn len(&self) -> usize {
+        self.remaining
+    }
+}
+
+impl<A, B> DoubleEndedIterator for Product<A, B>
+where
+    A: ExactSizeIterator + DoubleEndedIterator,
+    A::Item: Clone,
+    B: Clone,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        loop {
+            if !self.fill_back() {
+                self.remaining = 0;
+                return None;
+            }
+            let lower = if self.shared { self.front_idx } else { 0 };
+            if self.back_idx <= lower {
+                self.back_head = None;
+                continue;
+            }
+
+            self.back_idx -= 1;
+            let head = self.back_head.clone().expect("back_head filled above");
+            let item = (head, self.tail[self.back_idx].clone());
+            self.remaining -= 1;
+            return Some(item);
+        }
+    }
+}
+
+/// Implementation detail of [`cartesian!`]: combines two dimensions, both fully buffered.
+///
+/// Unlike [`Product`], neither side stays lazy here — both `head` and `tail` are drained into
+/// a `Vec` up front. That means `A` and `B` only need to be `Clone`-able, not
+/// `ExactSizeIterator + DoubleEndedIterator`, which is why [`cartesian!`] uses this for every
+/// dimension except the very first: a `HashMap::iter()`, a `.filter()` chain, or any other
+/// iterator lacking those bounds works fine here, just not as `cartesian!`'s leading argument.
+pub struct TailProduct<A, B> {
+    head: Vec<A>,
+    tail: Vec<B>,
+    front: usize,
+    back: usize,
+}
+
+impl<A: Clone, B: Clone> TailProduct<A, B> {
+    pub fn new<HI: IntoIterator<Item = A>, TI: IntoIterator<Item = B>>(head: HI, tail: TI) -> Self {
+        let head: Vec<A> = head.into_iter().collect();
+        let tail: Vec<B> = tail.into_iter().collect();
+        let back = head.len() * tail.len();
+        TailProduct {
+            head,
+            tail,
+            front: 0,
+            back,
+        }
+    }
+
+    fn item_at(&self, flat_idx: usize) -> (A, B) {
+        let tail_len = self.tail.len();
+        (
+            self.head[flat_idx / tail_len].clone(),
+            self.tail[flat_idx % tail_len].clone(),
+        )
+    }
+}
+
+impl<A: Clone, B: Clone> Iterator for TailProduct<A, B> {
+    type Item = (A, B);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.front >= self.back {
+            return None;
+        }
+        let item = self.item_at(self.front);
+        self.front += 1;
+        Some(item)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.back - self.front;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<A: Clone, B: Clone> ExactSizeIterator for TailProduct<A, B> {
+    fn len(&self) -> usize {
+        self.back - self.front
+    }
+}
+
+impl<A: Clone, B: Clone> DoubleEndedIterator for TailProduct<A, B> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.front >= self.back {
+            return None;
+        }
+        self.back -= 1;
+        Some(self.item_at(self.back))
+    }
+}
+
+/// Iterator over the `N`-th Cartesian power of a single iterable, i.e. every ordered
+/// `N`-length combination with repetition of its items.
+///
+/// Returned by [`cartesian_power!`]. The source iterator is drained into a buffer once,
+/// and an odometer over that buffer is advanced on every call to `next`, so the source
+/// only needs to be iterated a single time no matter how large `N` is.
+pub struct CartesianPower<T, const N: usize> {
+    items: Vec<T>,
+    indices: [usize; N],
+    done: bool,
+}
+
+impl<T, const N: usize> CartesianPower<T, N> {
+    pub fn new<I: IntoIterator<Item = T>>(source: I) -> Self {
+        let items: Vec<T> = source.into_iter().collect();
+        let done = N > 0 && items.is_empty();
+        CartesianPower {
+            items,
+            indices: [0; N],
+            done,
+        }
+    }
+}
+
+impl<T: Clone, const N: usize> Iterator for CartesianPower<T, N> {
+    type Item = [T; N];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let indices = self.indices;
+        let items = &self.items;
+        let result = core::array::from_fn(|i| items[indices[i]].clone());
+
+        // advance the odometer, carrying from the rightmost position leftwards
+        let base = self.items.len();
+        let mut pos = N;
+        loop {
+            if pos == 0 {
+                self.done = true;
+                break;
+            }
+            pos -= 1;
+            self.indices[pos] += 1;
+            if self.indices[pos] < base {
+                break;
+            }
+            self.indices[pos] = 0;
+        }
+
+        Some(result)
+    }
+}
+
+/// Takes an iterator expression with `Clone` items and a length `N`, and creates an iterator over
+/// every ordered `N`-length combination with repetition of its items.
+///
+/// This is the single-iterator counterpart to [`cartesian!`]: instead of spelling out
+/// `N` clones of the same iterator by hand, `cartesian_power!` drains the source once
+/// and yields all `N`-length arrays `[T; N]` over it, e.g. all length-3 strings over an alphabet.
+///
+/// # Examples
+/// ```
+/// use cartesian::cartesian_power;
+///
+/// let words: Vec<_> = cartesian_power!("ab".chars(), 2)
+///     .map(|[a, b]| format!("{}{}", a, b))
+///     .collect();
+///
+/// assert_eq!(words, vec!["aa", "ab", "ba", "bb"]);
+/// ```
+#[macro_export]
+macro_rules! cartesian_power {
+    ($source:expr, $n:expr) => {
+        $crate::CartesianPower::<_, $n>::new($source)
+    };
+}
+
+/// Cartesian product over a runtime-determined number of iterators, yielding `Vec<T>`.
+///
+/// Unlike [`cartesian!`], which needs the number of dimensions to be known at compile
+/// time (one tuple element per argument), `multi_product` takes the dimensions as a
+/// single `Vec` of iterators, which suits things like parameter-grid search or
+/// nonogram/CSP solvers where the dimension count is only known at runtime.
+///
+/// Returned by [`multi_product`]. Each input iterator is buffered into a `Vec` once,
+/// and an odometer over those buffers is advanced on every call to `next`.
+#[derive(Clone)]
+pub struct MultiProduct<T> {
+    items: Vec<Vec<T>>,
+    indices: Vec<usize>,
+    done: bool,
+}
+
+/// Creates the Cartesian product of a runtime-sized collection of iterators.
+///
+/// Every input is drained into a buffer up front, and the product is walked
+/// odometer-style from the last dimension, so restarting it is as simple as
+/// cloning the returned [`MultiProduct`].
+///
+/// If any input is empty, the whole product is empty. With zero inputs, it yields a
+/// single empty `Vec`.
+///
+/// # Examples
+/// ```
+/// use cartesian::multi_product;
+///
+/// let grid: Vec<_> = multi_product(vec![0..2, 0..2]).collect();
+///
+/// assert_eq!(grid, vec![vec![0, 0], vec![0, 1], vec![1, 0], vec![1, 1]]);
+/// ```
+pub fn multi_product<I>(inputs: Vec<I>) -> MultiProduct<I::Item>
+where
+    I: IntoIterator,
+{
+    let items: Vec<Vec<I::Item>> = inputs.into_iter().map(|i| i.into_iter().collect()).collect();
+    let done = items.iter().any(|dim| dim.is_empty());
+    let indices = alloc::vec![0; items.len()];
+    MultiProduct {
+        items,
+        indices,
+        done,
+    }
+}
+
+impl<T: Clone> Iterator for MultiProduct<T> {
+    type Item = Vec<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let result: Vec<T> = self
+            .indices
+            .iter()
+            .zip(&self.items)
+            .map(|(&i, dim)| dim[i].clone())
+            .collect();
+
+        // advance the odometer, carrying from the last dimension leftwards
+        let mut pos = self.indices.len();
+        loop {
+            if pos == 0 {
+                self.done = true;
+                break;
+            }
+            pos -= 1;
+            self.indices[pos] += 1;
+            if self.indices[pos] < self.items[pos].len() {
+                break;
+            }
+            self.indices[pos] = 0;
+        }
+
+        Some(result)
+    }
+}
+
 #[cfg(test)]
 use alloc::{format, string::String, vec};
 
@@ -180,3 +635,171 @@ fn by_reference() {
 
     assert_eq!(acc, "a0 a1 b0 b1 ");
 }
+
+#[test]
+fn exact_size() {
+    let mut iter = cartesian!(0..3, 0..4, 0..2);
+
+    assert_eq!(iter.len(), 3 * 4 * 2);
+    assert_eq!(iter.size_hint(), (24, Some(24)));
+
+    for _ in 0..5 {
+        iter.next();
+    }
+
+    assert_eq!(iter.len(), 19);
+    assert_eq!(iter.size_hint(), (19, Some(19)));
+
+    let remaining = iter.count();
+    assert_eq!(remaining, 19);
+}
+
+#[test]
+fn double_ended() {
+    let forward: alloc::vec::Vec<_> = cartesian!(0..2, 0..3).collect();
+    let backward: alloc::vec::Vec<_> = cartesian!(0..2, 0..3).rev().collect();
+
+    let mut expected = forward.clone();
+    expected.reverse();
+    assert_eq!(backward, expected);
+
+    let mut iter = cartesian!(0..2, 0..3);
+    let first = iter.next();
+    let last = iter.next_back();
+    assert_eq!(first, Some((0, 0)));
+    assert_eq!(last, Some((1, 2)));
+    assert_eq!(iter.len(), 4);
+
+    // next and next_back must never cross
+    let mut exhausted = cartesian!(0..1, 0..1);
+    assert_eq!(exhausted.next(), Some((0, 0)));
+    assert_eq!(exhausted.next_back(), None);
+}
+
+#[test]
+fn double_ended_interleaved() {
+    // interleaving next/next_back must produce the same items as the forward order,
+    // split at wherever the two cursors happen to meet
+    let expected: alloc::vec::Vec<_> = cartesian!(0..3, 0..4).collect();
+
+    let mut iter = cartesian!(0..3, 0..4);
+    let mut front = alloc::vec::Vec::new();
+    let mut back = alloc::vec::Vec::new();
+    for i in 0..expected.len() {
+        if i % 3 == 0 {
+            back.push(iter.next_back().unwrap());
+        } else {
+            front.push(iter.next().unwrap());
+        }
+    }
+    assert_eq!(iter.next(), None);
+    assert_eq!(iter.next_back(), None);
+
+    back.reverse();
+    front.extend(back);
+    assert_eq!(front, expected);
+}
+
+#[test]
+fn double_ended_through_tail_product() {
+    // With 3+ arguments, everything after the first is combined via `TailProduct`, which
+    // must itself support `next_back` for `.rev()` to work on the whole product.
+    let forward: alloc::vec::Vec<_> = cartesian!(0..2, 0..3, 0..2).collect();
+    let backward: alloc::vec::Vec<_> = cartesian!(0..2, 0..3, 0..2).rev().collect();
+
+    let mut expected = forward.clone();
+    expected.reverse();
+    assert_eq!(backward, expected);
+}
+
+#[test]
+fn large_leading_dimension_is_not_buffered() {
+    // A leading dimension too large to fit in memory as a Vec must still work, as
+    // long as it is only pulled from one end.
+    let huge = usize::MAX / 4;
+    let mut iter = cartesian!(0..huge, 0..2);
+    assert_eq!(iter.len(), huge * 2);
+    assert_eq!(iter.next(), Some((0, 0)));
+    assert_eq!(iter.next(), Some((0, 1)));
+    assert_eq!(iter.next(), Some((1, 0)));
+}
+
+#[test]
+fn non_leading_dimensions_need_only_clone() {
+    // `Filter` is `Clone` but not `ExactSizeIterator`. It must still be usable in any
+    // position except the first.
+    let mut acc = String::new();
+
+    let evens = (0..4).filter(|n| n % 2 == 0);
+    for (a, b) in cartesian!(0..2, evens) {
+        acc += &format!("{}{} ", a, b);
+    }
+    assert_eq!(acc, "00 02 10 12 ");
+
+    let mut acc = String::new();
+    let evens = (0..4).filter(|n| n % 2 == 0);
+    for (a, b, c) in cartesian!(0..2, evens, "x".chars()) {
+        acc += &format!("{}{}{} ", a, b, c);
+    }
+    assert_eq!(acc, "00x 02x 10x 12x ");
+}
+
+#[test]
+fn power_of_alphabet() {
+    let mut acc = String::new();
+
+    for [a, b] in cartesian_power!("xy".chars(), 2) {
+        acc += &format!("{}{} ", a, b);
+    }
+
+    assert_eq!(acc, "xx xy yx yy ");
+}
+
+#[test]
+fn power_zero_yields_one_empty_result() {
+    let results: alloc::vec::Vec<[char; 0]> = cartesian_power!("xy".chars(), 0).collect();
+    assert_eq!(results, vec![[]]);
+}
+
+#[test]
+fn power_of_empty_source_yields_nothing() {
+    let results: alloc::vec::Vec<[char; 2]> = cartesian_power!("".chars(), 2).collect();
+    assert!(results.is_empty());
+}
+
+#[test]
+fn multi_product_of_ranges() {
+    let results: alloc::vec::Vec<_> = multi_product(vec![0..2, 0..3]).collect();
+    assert_eq!(
+        results,
+        vec![
+            vec![0, 0],
+            vec![0, 1],
+            vec![0, 2],
+            vec![1, 0],
+            vec![1, 1],
+            vec![1, 2],
+        ]
+    );
+}
+
+#[test]
+fn multi_product_is_restartable_via_clone() {
+    let product = multi_product(vec![0..2, 0..2]);
+    let first: alloc::vec::Vec<_> = product.clone().collect();
+    let second: alloc::vec::Vec<_> = product.collect();
+    assert_eq!(first, second);
+}
+
+#[test]
+fn multi_product_empty_dimension_yields_nothing() {
+    let results: alloc::vec::Vec<_> = multi_product(vec![0..2, 0..0]).collect();
+    assert!(results.is_empty());
+}
+
+#[test]
+fn multi_product_no_inputs_yields_one_empty_vec() {
+    let inputs: alloc::vec::Vec<core::ops::Range<i32>> = alloc::vec::Vec::new();
+    let results: alloc::vec::Vec<_> = multi_product(inputs).collect();
+    assert_eq!(results, vec![vec![]]);
+}